@@ -6,19 +6,430 @@ use crossterm::{
     style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor},
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
+use serde::Deserialize;
+use std::collections::HashMap;
 use std::fs::{self, File};
 use std::io::{self, BufRead, BufReader, Write};
+use std::path::PathBuf;
 use std::time::{Duration, Instant};
 use std::{env, io::stdout};
+use syntect::highlighting::{
+    Highlighter, HighlightIterator, HighlightState, Style as SynStyle, Theme, ThemeSet,
+};
+use syntect::parsing::{ParseState, ScopeStack, SyntaxReference, SyntaxSet};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 
 // Simple editor mode enum
 #[derive(PartialEq, Clone, Copy)]
 enum Mode {
     Normal,
     Insert,
+    Search,
+}
+
+#[derive(PartialEq, Eq, Clone, Copy)]
+enum CharClass {
+    Whitespace,
+    Word,
+    Punctuation,
+}
+
+// WORD motions (W/B/E) treat anything non-whitespace as a single class.
+fn classify(c: char, big_word: bool) -> CharClass {
+    if c.is_whitespace() {
+        CharClass::Whitespace
+    } else if big_word || c.is_alphanumeric() || c == '_' {
+        CharClass::Word
+    } else {
+        CharClass::Punctuation
+    }
+}
+
+// Classifies a whole grapheme cluster by its first char; combining marks
+// riding along with a base character don't change the class of the cluster.
+fn classify_grapheme(g: &str, big_word: bool) -> CharClass {
+    classify(g.chars().next().unwrap_or(' '), big_word)
+}
+
+// `cursor_x` is a grapheme-cluster index, never a byte offset. These helpers
+// map cluster indices to byte offsets for insert/remove/split; they and
+// rendering all segment lines into extended grapheme clusters, so they agree
+// on where cluster boundaries fall.
+fn line_len_graphemes(line: &str) -> usize {
+    line.graphemes(true).count()
+}
+
+// Byte offset of the grapheme at `idx`, or the line's byte length if `idx`
+// is at or past the end (the natural "insert here to append" position).
+fn grapheme_byte_offset(line: &str, idx: usize) -> usize {
+    line.grapheme_indices(true)
+        .nth(idx)
+        .map(|(offset, _)| offset)
+        .unwrap_or(line.len())
+}
+
+// Byte range `[start, end)` covering the grapheme at `idx`, for removing or
+// replacing exactly one cluster without splitting it mid-codepoint.
+fn grapheme_byte_range(line: &str, idx: usize) -> (usize, usize) {
+    let start = grapheme_byte_offset(line, idx);
+    let end = grapheme_byte_offset(line, idx + 1);
+    (start, end)
+}
+
+// Display width of one grapheme cluster: wide glyphs (CJK, many emoji) count
+// as 2 columns, zero-width joiners/combining marks count as 0.
+fn grapheme_width(g: &str) -> usize {
+    UnicodeWidthStr::width(g)
+}
+
+// Grapheme index of the cluster starting at `byte_idx` in `line`.
+fn grapheme_idx_at_byte(line: &str, byte_idx: usize) -> usize {
+    line.grapheme_indices(true)
+        .take_while(|(offset, _)| *offset < byte_idx)
+        .count()
+}
+
+// One line's syntax-highlight result: a display color per byte range, plus
+// the parser/highlighter state at the end of the line so the next line can
+// resume from it. Carrying that state is what lets multi-line constructs
+// (block comments, strings) highlight correctly instead of resetting at
+// every line boundary.
+struct LineHighlight {
+    spans: Vec<(Color, usize, usize)>,
+    parse_state: ParseState,
+    highlight_state: HighlightState,
+}
+
+fn syntect_color(style: SynStyle) -> Color {
+    Color::Rgb {
+        r: style.foreground.r,
+        g: style.foreground.g,
+        b: style.foreground.b,
+    }
+}
+
+// Grapheme-index `[start, end)` ranges of every occurrence of `query` in
+// `line`, used by `render` to highlight search matches.
+fn match_grapheme_ranges(line: &str, query: &str) -> Vec<(usize, usize)> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+    line.match_indices(query)
+        .map(|(byte_idx, matched)| {
+            let start = grapheme_idx_at_byte(line, byte_idx);
+            let end = grapheme_idx_at_byte(line, byte_idx + matched.len());
+            (start, end)
+        })
+        .collect()
+}
+
+// A named operation a key can be bound to. Every motion/command the editor
+// knows about has one of these, so remapping a key is just pointing a
+// `KeyBinding` at a different name.
+type Action = fn(&mut Editor);
+
+// A key plus the modifiers held down, e.g. `Char('r')` + `CONTROL` for redo.
+// This is what keymaps are keyed by, rather than raw `KeyCode`, so `u` and
+// `Ctrl-u` can be bound to different actions.
+#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+struct KeyBinding {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    fn plain(code: KeyCode) -> Self {
+        KeyBinding {
+            code,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    fn ctrl(code: KeyCode) -> Self {
+        KeyBinding {
+            code,
+            modifiers: KeyModifiers::CONTROL,
+        }
+    }
+}
+
+fn action_move_char_left(ed: &mut Editor) {
+    if ed.cursor_x > 0 {
+        ed.cursor_x -= 1;
+    }
+}
+
+fn action_move_char_right(ed: &mut Editor) {
+    let line_len = line_len_graphemes(&ed.buffer[ed.cursor_y]);
+    if ed.cursor_x < line_len {
+        ed.cursor_x += 1;
+    }
+}
+
+fn action_move_line_up(ed: &mut Editor) {
+    if ed.cursor_y > 0 {
+        ed.cursor_y -= 1;
+        let line_len = line_len_graphemes(&ed.buffer[ed.cursor_y]);
+        if ed.cursor_x > line_len {
+            ed.cursor_x = line_len;
+        }
+    }
+}
+
+fn action_move_line_down(ed: &mut Editor) {
+    if ed.cursor_y < ed.buffer.len() - 1 {
+        ed.cursor_y += 1;
+        let line_len = line_len_graphemes(&ed.buffer[ed.cursor_y]);
+        if ed.cursor_x > line_len {
+            ed.cursor_x = line_len;
+        }
+    }
+}
+
+fn action_goto_line_start(ed: &mut Editor) {
+    ed.cursor_x = 0;
+}
+
+fn action_goto_line_end(ed: &mut Editor) {
+    let line_len = line_len_graphemes(&ed.buffer[ed.cursor_y]);
+    ed.cursor_x = if line_len > 0 { line_len } else { 0 };
+}
+
+fn action_move_word_next(ed: &mut Editor) {
+    ed.move_next_word_start(false);
+}
+
+fn action_move_word_next_big(ed: &mut Editor) {
+    ed.move_next_word_start(true);
+}
+
+fn action_move_word_prev(ed: &mut Editor) {
+    ed.move_prev_word_start(false);
+}
+
+fn action_move_word_prev_big(ed: &mut Editor) {
+    ed.move_prev_word_start(true);
+}
+
+fn action_move_word_end(ed: &mut Editor) {
+    ed.move_next_word_end(false);
+}
+
+fn action_move_word_end_big(ed: &mut Editor) {
+    ed.move_next_word_end(true);
+}
+
+fn action_insert_mode(ed: &mut Editor) {
+    ed.mode = Mode::Insert;
+    ed.coalescing_insert = false;
+}
+
+fn action_exit_insert_mode(ed: &mut Editor) {
+    ed.exit_insert_mode();
+}
+
+fn action_insert_backspace(ed: &mut Editor) {
+    ed.delete_backward();
+}
+
+fn action_insert_newline(ed: &mut Editor) {
+    ed.insert_newline();
+}
+
+fn action_undo(ed: &mut Editor) {
+    ed.undo();
+}
+
+fn action_redo(ed: &mut Editor) {
+    ed.redo();
+}
+
+fn action_save(ed: &mut Editor) {
+    ed.save_buffer();
+}
+
+fn action_quit(ed: &mut Editor) {
+    ed.should_quit = ed.request_quit();
+}
+
+fn action_command_prompt(ed: &mut Editor) {
+    stdout().flush().unwrap();
+    ed.prompt_and_execute_command();
+}
+
+fn action_search_start(ed: &mut Editor) {
+    ed.start_search();
+}
+
+fn action_search_next(ed: &mut Editor) {
+    ed.search_next();
+}
+
+fn action_search_prev(ed: &mut Editor) {
+    ed.search_prev();
+}
+
+// The full set of actions a key can be bound to, looked up by name.
+fn build_action_table() -> HashMap<&'static str, Action> {
+    let mut actions: HashMap<&'static str, Action> = HashMap::new();
+    actions.insert("move_char_left", action_move_char_left);
+    actions.insert("move_char_right", action_move_char_right);
+    actions.insert("move_line_up", action_move_line_up);
+    actions.insert("move_line_down", action_move_line_down);
+    actions.insert("goto_line_start", action_goto_line_start);
+    actions.insert("goto_line_end", action_goto_line_end);
+    actions.insert("move_word_next", action_move_word_next);
+    actions.insert("move_word_next_big", action_move_word_next_big);
+    actions.insert("move_word_prev", action_move_word_prev);
+    actions.insert("move_word_prev_big", action_move_word_prev_big);
+    actions.insert("move_word_end", action_move_word_end);
+    actions.insert("move_word_end_big", action_move_word_end_big);
+    actions.insert("insert_mode", action_insert_mode);
+    actions.insert("exit_insert_mode", action_exit_insert_mode);
+    actions.insert("insert_backspace", action_insert_backspace);
+    actions.insert("insert_newline", action_insert_newline);
+    actions.insert("undo", action_undo);
+    actions.insert("redo", action_redo);
+    actions.insert("save", action_save);
+    actions.insert("quit", action_quit);
+    actions.insert("command_prompt", action_command_prompt);
+    actions.insert("search_start", action_search_start);
+    actions.insert("search_next", action_search_next);
+    actions.insert("search_prev", action_search_prev);
+    actions
+}
+
+// Built-in Normal-mode keymap, used as-is when there's no config override.
+fn default_normal_keymap() -> HashMap<KeyBinding, String> {
+    use KeyCode::*;
+    let mut map = HashMap::new();
+    map.insert(KeyBinding::ctrl(Char('r')), "redo".to_string());
+    map.insert(KeyBinding::plain(Char('u')), "undo".to_string());
+    map.insert(KeyBinding::plain(Char('i')), "insert_mode".to_string());
+    map.insert(KeyBinding::plain(Char('w')), "move_word_next".to_string());
+    map.insert(KeyBinding::plain(Char('W')), "move_word_next_big".to_string());
+    map.insert(KeyBinding::plain(Char('b')), "move_word_prev".to_string());
+    map.insert(KeyBinding::plain(Char('B')), "move_word_prev_big".to_string());
+    map.insert(KeyBinding::plain(Char('e')), "move_word_end".to_string());
+    map.insert(KeyBinding::plain(Char('E')), "move_word_end_big".to_string());
+    map.insert(KeyBinding::plain(Char('h')), "move_char_left".to_string());
+    map.insert(KeyBinding::plain(Left), "move_char_left".to_string());
+    map.insert(KeyBinding::plain(Char('j')), "move_line_down".to_string());
+    map.insert(KeyBinding::plain(Down), "move_line_down".to_string());
+    map.insert(KeyBinding::plain(Char('k')), "move_line_up".to_string());
+    map.insert(KeyBinding::plain(Up), "move_line_up".to_string());
+    map.insert(KeyBinding::plain(Char('l')), "move_char_right".to_string());
+    map.insert(KeyBinding::plain(Right), "move_char_right".to_string());
+    map.insert(KeyBinding::plain(Char('0')), "goto_line_start".to_string());
+    map.insert(KeyBinding::plain(Char('$')), "goto_line_end".to_string());
+    map.insert(KeyBinding::plain(Char(':')), "command_prompt".to_string());
+    map.insert(KeyBinding::plain(Char('/')), "search_start".to_string());
+    map.insert(KeyBinding::plain(Char('n')), "search_next".to_string());
+    map.insert(KeyBinding::plain(Char('N')), "search_prev".to_string());
+    map.insert(KeyBinding::plain(Char('q')), "quit".to_string());
+    map
+}
+
+// Built-in Insert-mode keymap. Plain printable characters are deliberately
+// left unbound here: `handle_keypress` falls back to self-inserting any
+// `Char` that isn't claimed by the keymap, so remapping never has to mention
+// every letter of the alphabet.
+fn default_insert_keymap() -> HashMap<KeyBinding, String> {
+    use KeyCode::*;
+    let mut map = HashMap::new();
+    map.insert(KeyBinding::plain(Esc), "exit_insert_mode".to_string());
+    map.insert(KeyBinding::plain(Backspace), "insert_backspace".to_string());
+    map.insert(KeyBinding::plain(Enter), "insert_newline".to_string());
+    map.insert(KeyBinding::plain(Left), "move_char_left".to_string());
+    map.insert(KeyBinding::plain(Right), "move_char_right".to_string());
+    map.insert(KeyBinding::plain(Up), "move_line_up".to_string());
+    map.insert(KeyBinding::plain(Down), "move_line_down".to_string());
+    map
+}
+
+// On-disk shape of `keymap.toml`: `key string -> action name`, per mode.
+// Unrecognised keys/actions are ignored rather than failing the whole file,
+// so a typo in one binding doesn't cost every other override.
+#[derive(Deserialize, Default)]
+struct KeymapConfig {
+    #[serde(default)]
+    normal: HashMap<String, String>,
+    #[serde(default)]
+    insert: HashMap<String, String>,
+}
+
+fn keymap_config_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("smoke").join("keymap.toml"))
+}
+
+fn parse_keycode(s: &str) -> Option<KeyCode> {
+    match s {
+        "Left" => Some(KeyCode::Left),
+        "Right" => Some(KeyCode::Right),
+        "Up" => Some(KeyCode::Up),
+        "Down" => Some(KeyCode::Down),
+        "Esc" => Some(KeyCode::Esc),
+        "Enter" => Some(KeyCode::Enter),
+        "Backspace" => Some(KeyCode::Backspace),
+        "Tab" => Some(KeyCode::Tab),
+        _ if s.chars().count() == 1 => s.chars().next().map(KeyCode::Char),
+        _ => None,
+    }
+}
+
+// "C-r" is Ctrl-r, anything else is looked up as a plain key.
+fn parse_keybinding(s: &str) -> Option<KeyBinding> {
+    match s.strip_prefix("C-") {
+        Some(rest) => parse_keycode(rest).map(KeyBinding::ctrl),
+        None => parse_keycode(s).map(KeyBinding::plain),
+    }
+}
+
+fn apply_overrides(mut map: HashMap<KeyBinding, String>, overrides: &HashMap<String, String>) -> HashMap<KeyBinding, String> {
+    for (key_str, action) in overrides {
+        if let Some(binding) = parse_keybinding(key_str) {
+            map.insert(binding, action.clone());
+        }
+    }
+    map
+}
+
+// Loads `keymap.toml` from the user config dir and merges it over the
+// built-in defaults, falling back to pure defaults if the file is missing
+// or fails to parse.
+fn load_keymaps() -> (HashMap<KeyBinding, String>, HashMap<KeyBinding, String>) {
+    let config = keymap_config_path()
+        .filter(|path| path.exists())
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|contents| toml::from_str::<KeymapConfig>(&contents).ok())
+        .unwrap_or_default();
+
+    (
+        apply_overrides(default_normal_keymap(), &config.normal),
+        apply_overrides(default_insert_keymap(), &config.insert),
+    )
 }
 
 // Basic editor implementation
+// A snapshot taken before a mutating edit, restored on undo/redo.
+#[derive(Clone)]
+struct Snapshot {
+    buffer: Vec<String>,
+    cursor_x: usize,
+    cursor_y: usize,
+}
+
+// Maximum number of undo steps retained before the oldest is dropped.
+const UNDO_DEPTH: usize = 100;
+
+// Number of times `q`/`:q` must be repeated with unsaved changes before the
+// editor actually quits, kilo's `KILO_QUIT_TIMES`.
+const QUIT_TIMES: u8 = 3;
+
+// How long a status message stays on screen before `render` clears it.
+const STATUS_MESSAGE_TIMEOUT: Duration = Duration::from_secs(5);
+
 struct Editor {
     buffer: Vec<String>,
     cursor_x: usize,
@@ -28,20 +439,49 @@ struct Editor {
     last_blink: Instant,
     blink_interval: Duration,
     filename: Option<String>,
+    row_offset: usize,
+    col_offset: usize,
+    undo_stack: Vec<Snapshot>,
+    redo_stack: Vec<Snapshot>,
+    coalescing_insert: bool,
+    should_quit: bool,
+    normal_keymap: HashMap<KeyBinding, String>,
+    insert_keymap: HashMap<KeyBinding, String>,
+    actions: HashMap<&'static str, Action>,
+    search_query: String,
+    last_search: Option<String>,
+    pre_search_cursor: (usize, usize),
+    dirty: usize,
+    quit_times: u8,
+    status_message: String,
+    status_message_time: Instant,
+    syntax_set: SyntaxSet,
+    theme: Theme,
+    syntax: Option<SyntaxReference>,
+    highlight_cache: Vec<LineHighlight>,
 }
 
 impl Editor {
     fn new(filename: Option<String>) -> Self {
         let mut buffer = vec![String::new()];
-        if let Some(ref name) = filename {
-            if let Ok(file) = File::open(name) {
-                buffer = BufReader::new(file)
-                    .lines()
-                    .filter_map(Result::ok)
-                    .collect();
-            }
+        if let Some(ref name) = filename
+            && let Ok(file) = File::open(name)
+        {
+            buffer = BufReader::new(file)
+                .lines()
+                .map_while(Result::ok)
+                .collect();
         }
 
+        let (normal_keymap, insert_keymap) = load_keymaps();
+
+        let syntax_set = SyntaxSet::load_defaults_newlines();
+        let theme = ThemeSet::load_defaults().themes["base16-ocean.dark"].clone();
+        let syntax = filename
+            .as_deref()
+            .and_then(|name| syntax_set.find_syntax_for_file(name).ok().flatten())
+            .cloned();
+
         Editor {
             buffer,
             cursor_x: 0,
@@ -51,6 +491,292 @@ impl Editor {
             last_blink: Instant::now(),
             blink_interval: Duration::from_millis(500), // Blink every 500ms
             filename,
+            row_offset: 0,
+            col_offset: 0,
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            coalescing_insert: false,
+            should_quit: false,
+            normal_keymap,
+            insert_keymap,
+            actions: build_action_table(),
+            search_query: String::new(),
+            last_search: None,
+            pre_search_cursor: (0, 0),
+            dirty: 0,
+            quit_times: QUIT_TIMES,
+            status_message: String::new(),
+            status_message_time: Instant::now(),
+            syntax_set,
+            theme,
+            syntax,
+            highlight_cache: Vec::new(),
+        }
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            buffer: self.buffer.clone(),
+            cursor_x: self.cursor_x,
+            cursor_y: self.cursor_y,
+        }
+    }
+
+    fn set_status(&mut self, message: String) {
+        self.status_message = message;
+        self.status_message_time = Instant::now();
+    }
+
+    // Every modifying edit goes through here so `dirty` stays in sync and a
+    // fresh quit confirmation is required again after the buffer changes.
+    fn mark_dirty(&mut self) {
+        self.dirty += 1;
+        self.quit_times = QUIT_TIMES;
+    }
+
+    // `q`/`:q`: refuses to quit with unsaved changes until it has been asked
+    // QUIT_TIMES times since the last edit, kilo-style; keys that don't edit
+    // the buffer don't reset the count. Returns whether the editor should
+    // actually quit.
+    fn request_quit(&mut self) -> bool {
+        if self.dirty > 0 && self.quit_times > 0 {
+            self.set_status(format!(
+                "Unsaved changes ({} edit{})! Press q {} more time{} to quit without saving.",
+                self.dirty,
+                if self.dirty == 1 { "" } else { "s" },
+                self.quit_times,
+                if self.quit_times == 1 { "" } else { "s" }
+            ));
+            self.quit_times -= 1;
+            return false;
+        }
+        true
+    }
+
+    fn highlighter(&self) -> Highlighter<'_> {
+        Highlighter::new(&self.theme)
+    }
+
+    // Drops cached highlight spans/state from line `y` onward so they get
+    // recomputed on next render. Any edit can change the line's end-of-line
+    // scope stack, which every line below depends on, so a partial
+    // invalidation can't stop earlier than the edited line itself.
+    fn invalidate_highlight_from(&mut self, y: usize) {
+        self.highlight_cache.truncate(y);
+    }
+
+    // Lazily (re)highlights buffer lines from wherever the cache left off
+    // up through `upto` (inclusive), resuming the syntect parser/highlight
+    // state from the previous line's cached result.
+    fn ensure_highlighted(&mut self, upto: usize) {
+        let Some(syntax) = self.syntax.clone() else {
+            return;
+        };
+
+        let mut idx = self.highlight_cache.len().min(self.buffer.len());
+        let (mut parse_state, mut highlight_state) = match self.highlight_cache.last() {
+            Some(last) => (last.parse_state.clone(), last.highlight_state.clone()),
+            None => (
+                ParseState::new(&syntax),
+                HighlightState::new(&self.highlighter(), ScopeStack::new()),
+            ),
+        };
+
+        while idx <= upto && idx < self.buffer.len() {
+            // syntect's newline-aware syntaxes expect each line to end in
+            // '\n', even the last one.
+            let mut line = self.buffer[idx].clone();
+            line.push('\n');
+
+            let ops = parse_state
+                .parse_line(&line, &self.syntax_set)
+                .unwrap_or_default();
+            let mut offset = 0usize;
+            let spans: Vec<(Color, usize, usize)> = {
+                let highlighter = self.highlighter();
+                HighlightIterator::new(&mut highlight_state, &ops, &line, &highlighter)
+                    .map(|(style, text)| {
+                        let start = offset;
+                        offset += text.len();
+                        (syntect_color(style), start, offset)
+                    })
+                    .collect()
+            };
+
+            self.highlight_cache.push(LineHighlight {
+                spans,
+                parse_state: parse_state.clone(),
+                highlight_state: highlight_state.clone(),
+            });
+            idx += 1;
+        }
+    }
+
+    // Call before a mutating edit. `coalesce` groups this edit with the
+    // previous one (used for single-character inserts) instead of opening
+    // a new undo step.
+    fn push_undo(&mut self, coalesce: bool) {
+        if coalesce && self.coalescing_insert {
+            return;
+        }
+        self.undo_stack.push(self.snapshot());
+        if self.undo_stack.len() > UNDO_DEPTH {
+            self.undo_stack.remove(0);
+        }
+        self.redo_stack.clear();
+        self.coalescing_insert = coalesce;
+    }
+
+    fn undo(&mut self) {
+        if let Some(prev) = self.undo_stack.pop() {
+            self.redo_stack.push(self.snapshot());
+            self.mark_dirty();
+            let from = self.cursor_y.min(prev.cursor_y);
+            self.buffer = prev.buffer;
+            self.cursor_x = prev.cursor_x;
+            self.cursor_y = prev.cursor_y;
+            self.coalescing_insert = false;
+            self.invalidate_highlight_from(from);
+        }
+    }
+
+    fn redo(&mut self) {
+        if let Some(next) = self.redo_stack.pop() {
+            self.undo_stack.push(self.snapshot());
+            self.mark_dirty();
+            let from = self.cursor_y.min(next.cursor_y);
+            self.buffer = next.buffer;
+            self.cursor_x = next.cursor_x;
+            self.cursor_y = next.cursor_y;
+            self.coalescing_insert = false;
+            self.invalidate_highlight_from(from);
+        }
+    }
+
+    // Finds the nearest occurrence of `query` at or after (or, if
+    // `backward`, strictly before) `(start_y, start_x)`, wrapping around the
+    // buffer ends. `start_x` is a grapheme index and is clamped to the line
+    // length, so an out-of-range value means "end of the line": a backward
+    // search then covers the whole start line, a forward search skips it
+    // until it wraps back around to it.
+    fn find_from(
+        &self,
+        start_y: usize,
+        start_x: usize,
+        query: &str,
+        backward: bool,
+    ) -> Option<(usize, usize)> {
+        let total = self.buffer.len();
+        if query.is_empty() || total == 0 {
+            return None;
+        }
+
+        for offset in 0..=total {
+            let y = if backward {
+                (start_y + total - offset % total) % total
+            } else {
+                (start_y + offset) % total
+            };
+            let line = &self.buffer[y];
+            let on_start_line = offset == 0;
+
+            if backward {
+                let byte_limit = if on_start_line {
+                    grapheme_byte_offset(line, start_x)
+                } else {
+                    line.len()
+                };
+                if let Some(byte_idx) = line[..byte_limit].rfind(query) {
+                    return Some((y, grapheme_idx_at_byte(line, byte_idx)));
+                }
+            } else {
+                let byte_start = if on_start_line {
+                    grapheme_byte_offset(line, start_x)
+                } else {
+                    0
+                };
+                if let Some(rel_idx) = line[byte_start..].find(query) {
+                    return Some((y, grapheme_idx_at_byte(line, byte_start + rel_idx)));
+                }
+            }
+        }
+
+        None
+    }
+
+    // `/`: enters Search mode, remembering where to snap back on Esc.
+    fn start_search(&mut self) {
+        self.pre_search_cursor = (self.cursor_x, self.cursor_y);
+        self.search_query.clear();
+        self.mode = Mode::Search;
+    }
+
+    // Re-runs the search from the pre-search cursor as the query changes,
+    // so the cursor tracks the nearest match at or after the start position.
+    fn search_live_update(&mut self) {
+        let (start_x, start_y) = self.pre_search_cursor;
+        if self.search_query.is_empty() {
+            self.cursor_x = start_x;
+            self.cursor_y = start_y;
+            return;
+        }
+        if let Some((y, x)) = self.find_from(start_y, start_x, &self.search_query.clone(), false) {
+            self.cursor_y = y;
+            self.cursor_x = x;
+        }
+    }
+
+    fn handle_search_key(&mut self, key: KeyCode) -> bool {
+        match key {
+            KeyCode::Esc => {
+                self.cursor_x = self.pre_search_cursor.0;
+                self.cursor_y = self.pre_search_cursor.1;
+                self.search_query.clear();
+                self.mode = Mode::Normal;
+                false
+            }
+            KeyCode::Enter => {
+                if !self.search_query.is_empty() {
+                    self.last_search = Some(std::mem::take(&mut self.search_query));
+                } else {
+                    self.search_query.clear();
+                }
+                self.mode = Mode::Normal;
+                false
+            }
+            KeyCode::Backspace => {
+                self.search_query.pop();
+                self.search_live_update();
+                false
+            }
+            KeyCode::Char(c) => {
+                self.search_query.push(c);
+                self.search_live_update();
+                false
+            }
+            _ => false,
+        }
+    }
+
+    // `n`: jump to the next occurrence of the last search query.
+    fn search_next(&mut self) {
+        let Some(query) = self.last_search.clone() else {
+            return;
+        };
+        if let Some((y, x)) = self.find_from(self.cursor_y, self.cursor_x + 1, &query, false) {
+            self.cursor_y = y;
+            self.cursor_x = x;
+        }
+    }
+
+    // `N`: jump to the previous occurrence of the last search query.
+    fn search_prev(&mut self) {
+        let Some(query) = self.last_search.clone() else {
+            return;
+        };
+        if let Some((y, x)) = self.find_from(self.cursor_y, self.cursor_x, &query, true) {
+            self.cursor_y = y;
+            self.cursor_x = x;
         }
     }
 
@@ -62,10 +788,31 @@ impl Editor {
         }
     }
 
+    // Keeps row_offset/col_offset tracking the cursor so scrolling kicks in
+    // once the buffer is taller or wider than the terminal, kilo-style.
+    fn scroll(&mut self, visible_rows: usize, visible_cols: usize) {
+        if self.cursor_y < self.row_offset {
+            self.row_offset = self.cursor_y;
+        } else if self.cursor_y >= self.row_offset + visible_rows {
+            self.row_offset = self.cursor_y + 1 - visible_rows;
+        }
+
+        if self.cursor_x < self.col_offset {
+            self.col_offset = self.cursor_x;
+        } else if self.cursor_x >= self.col_offset + visible_cols {
+            self.col_offset = self.cursor_x + 1 - visible_cols;
+        }
+    }
+
     fn render<W: Write>(&mut self, out: &mut W) -> Result<(), Box<dyn std::error::Error>> {
         // Update cursor blinking state
         self.update_cursor_blink();
 
+        let (term_width, term_height) = crossterm::terminal::size()?;
+        let visible_rows = (term_height as usize).saturating_sub(2).max(1);
+        let visible_cols = (term_width as usize).max(1);
+        self.scroll(visible_rows, visible_cols);
+
         // Clear screen and reset cursor
         queue!(
             out,
@@ -73,69 +820,108 @@ impl Editor {
         )?;
         queue!(out, MoveTo(0, 0))?;
 
-        // Render buffer
-        for (y, line) in self.buffer.iter().enumerate() {
-            queue!(out, MoveTo(0, y as u16))?;
-
-            if y == self.cursor_y {
-                // Render line with cursor
-                for (x, ch) in line.chars().enumerate() {
-                    if x == self.cursor_x && self.cursor_visible {
-                        // Draw character with cursor highlighting
-                        match self.mode {
-                            Mode::Normal => {
-                                // Block cursor (inverted colors)
-                                queue!(
-                                    out,
-                                    SetBackgroundColor(Color::White),
-                                    SetForegroundColor(Color::Black),
-                                    Print(ch),
-                                    ResetColor
-                                )?;
-                            }
-                            Mode::Insert => {
-                                // Vertical bar cursor (character + bar)
-                                queue!(
-                                    out,
-                                    Print(ch),
-                                    MoveTo(x as u16, y as u16),
-                                    SetBackgroundColor(Color::White),
-                                    Print("|"),
-                                    ResetColor,
-                                    MoveTo(x as u16 + 1, y as u16)
-                                )?;
-                            }
-                        }
-                    } else {
-                        // Regular character
-                        queue!(out, Print(ch))?;
-                    }
-                }
+        // While a search is active, every match on a visible line gets a
+        // highlighted background so occurrences are visible at a glance.
+        let searching = self.mode == Mode::Search && !self.search_query.is_empty();
+
+        // Render only the rows that fit on screen, translated by row_offset.
+        let last_row = (self.row_offset + visible_rows).min(self.buffer.len());
+        self.ensure_highlighted(last_row.saturating_sub(1));
 
-                // Handle cursor at end of line
-                if self.cursor_x >= line.len() && self.cursor_visible {
+        for (screen_y, line) in self.buffer[self.row_offset..last_row].iter().enumerate() {
+            let y = self.row_offset + screen_y;
+            queue!(out, MoveTo(0, screen_y as u16))?;
+
+            let graphemes: Vec<&str> = line.graphemes(true).collect();
+            let byte_offsets: Vec<usize> = line.grapheme_indices(true).map(|(b, _)| b).collect();
+            let visible: Vec<&str> = graphemes.iter().skip(self.col_offset).copied().collect();
+            let cursor_x = self.cursor_x.saturating_sub(self.col_offset);
+            let match_ranges = if searching {
+                match_grapheme_ranges(line, &self.search_query)
+            } else {
+                Vec::new()
+            };
+            // Syntax color per grapheme, looked up from the cached spans for
+            // this line; `None` (no syntax matched, or nothing cached yet)
+            // just falls back to the terminal's default foreground.
+            let syntax_colors: Vec<Option<Color>> = match self.highlight_cache.get(y) {
+                Some(lh) => byte_offsets
+                    .iter()
+                    .map(|&b| {
+                        lh.spans
+                            .iter()
+                            .find(|(_, start, end)| b >= *start && b < *end)
+                            .map(|(color, _, _)| *color)
+                    })
+                    .collect(),
+                None => Vec::new(),
+            };
+
+            // Render the line cluster by cluster, tracking the on-screen
+            // column separately from the grapheme index since wide clusters
+            // (CJK, emoji) occupy two columns.
+            let mut col: u16 = 0;
+            for (i, g) in visible.iter().enumerate() {
+                let abs_idx = self.col_offset + i;
+                let is_match = match_ranges.iter().any(|(s, e)| abs_idx >= *s && abs_idx < *e);
+                let syntax_color = syntax_colors.get(abs_idx).copied().flatten();
+
+                if y == self.cursor_y && i == cursor_x && self.cursor_visible {
+                    // Draw cluster with cursor highlighting
                     match self.mode {
-                        Mode::Normal => {
+                        Mode::Normal | Mode::Search => {
+                            // Block cursor (inverted colors)
                             queue!(
                                 out,
                                 SetBackgroundColor(Color::White),
-                                Print(" "),
+                                SetForegroundColor(Color::Black),
+                                Print(*g),
                                 ResetColor
                             )?;
                         }
                         Mode::Insert => {
+                            // Vertical bar cursor (cluster + bar)
                             queue!(
                                 out,
+                                Print(*g),
+                                MoveTo(col + grapheme_width(g) as u16, screen_y as u16),
                                 SetBackgroundColor(Color::White),
                                 Print("|"),
-                                ResetColor
+                                ResetColor,
+                                MoveTo(col + grapheme_width(g) as u16 + 1, screen_y as u16)
                             )?;
                         }
                     }
+                } else if is_match {
+                    queue!(out, SetBackgroundColor(Color::DarkYellow), Print(*g), ResetColor)?;
+                } else if let Some(color) = syntax_color {
+                    queue!(out, SetForegroundColor(color), Print(*g), ResetColor)?;
+                } else {
+                    queue!(out, Print(*g))?;
+                }
+                col += grapheme_width(g) as u16;
+            }
+
+            // Handle cursor at end of line
+            if y == self.cursor_y && cursor_x >= visible.len() && self.cursor_visible {
+                match self.mode {
+                    Mode::Normal | Mode::Search => {
+                        queue!(
+                            out,
+                            SetBackgroundColor(Color::White),
+                            Print(" "),
+                            ResetColor
+                        )?;
+                    }
+                    Mode::Insert => {
+                        queue!(
+                            out,
+                            SetBackgroundColor(Color::White),
+                            Print("|"),
+                            ResetColor
+                        )?;
+                    }
                 }
-            } else {
-                // Render line normally
-                queue!(out, Print(line))?;
             }
         }
 
@@ -143,15 +929,30 @@ impl Editor {
         let mode_str = match self.mode {
             Mode::Normal => "NORMAL",
             Mode::Insert => "INSERT",
+            Mode::Search => "SEARCH",
         };
 
-        let (_term_width, term_height) = crossterm::terminal::size()?;
-        let status = format!(
-            "{} | Line: {}, Col: {} ",
-            mode_str,
-            self.cursor_y + 1,
-            self.cursor_x + 1
-        );
+        let status = if self.mode == Mode::Search {
+            format!("/{}", self.search_query)
+        } else if !self.status_message.is_empty()
+            && self.status_message_time.elapsed() < STATUS_MESSAGE_TIMEOUT
+        {
+            self.status_message.clone()
+        } else {
+            self.status_message.clear();
+            let modified = if self.dirty > 0 {
+                format!(" [+{}]", self.dirty)
+            } else {
+                String::new()
+            };
+            format!(
+                "{}{} | Line: {}, Col: {} ",
+                mode_str,
+                modified,
+                self.cursor_y + 1,
+                self.cursor_x + 1
+            )
+        };
         queue!(
             out,
             MoveTo(0, term_height - 2),
@@ -168,74 +969,135 @@ impl Editor {
         Ok(())
     }
 
-    fn handle_normal_key(&mut self, key: KeyCode) -> bool {
-        match key {
-            // Mode switching
-            KeyCode::Char('i') => {
-                self.mode = Mode::Insert;
-                false
+    fn line_graphemes(&self, y: usize) -> Vec<&str> {
+        self.buffer[y].graphemes(true).collect()
+    }
+
+    // `w`/`W`: start of the next word.
+    fn move_next_word_start(&mut self, big_word: bool) {
+        let mut y = self.cursor_y;
+        let mut x = self.cursor_x;
+        let mut graphemes = self.line_graphemes(y);
+
+        if x < graphemes.len() {
+            let start_class = classify_grapheme(graphemes[x], big_word);
+            while x < graphemes.len() && classify_grapheme(graphemes[x], big_word) == start_class {
+                x += 1;
             }
+        }
 
-            // Movement
-            KeyCode::Char('h') | KeyCode::Left => {
-                if self.cursor_x > 0 {
-                    self.cursor_x -= 1;
-                }
-                false
+        loop {
+            while x < graphemes.len()
+                && classify_grapheme(graphemes[x], big_word) == CharClass::Whitespace
+            {
+                x += 1;
             }
-            KeyCode::Char('j') | KeyCode::Down => {
-                if self.cursor_y < self.buffer.len() - 1 {
-                    self.cursor_y += 1;
-                    // Adjust x if necessary
-                    let line_len = self.buffer[self.cursor_y].len();
-                    if self.cursor_x > line_len {
-                        self.cursor_x = line_len;
-                    }
-                }
-                false
+            if x < graphemes.len() {
+                break;
             }
-            KeyCode::Char('k') | KeyCode::Up => {
-                if self.cursor_y > 0 {
-                    self.cursor_y -= 1;
-                    // Adjust x if necessary
-                    let line_len = self.buffer[self.cursor_y].len();
-                    if self.cursor_x > line_len {
-                        self.cursor_x = line_len;
-                    }
-                }
-                false
+            if y + 1 >= self.buffer.len() {
+                break;
+            }
+            y += 1;
+            graphemes = self.line_graphemes(y);
+            x = 0;
+            if !graphemes.is_empty() {
+                continue;
             }
-            KeyCode::Char('l') | KeyCode::Right => {
-                let line_len = self.buffer[self.cursor_y].len();
-                if self.cursor_x < line_len {
-                    self.cursor_x += 1;
+            break;
+        }
+
+        let line_len = graphemes.len();
+        self.cursor_y = y;
+        self.cursor_x = x.min(line_len);
+    }
+
+    // `b`/`B`: start of the previous word.
+    fn move_prev_word_start(&mut self, big_word: bool) {
+        let mut y = self.cursor_y;
+        let mut x = self.cursor_x;
+
+        loop {
+            if x == 0 {
+                if y == 0 {
+                    return;
                 }
-                false
+                y -= 1;
+                x = self.line_graphemes(y).len();
+            } else {
+                x -= 1;
             }
 
-            // Start/end of line
-            KeyCode::Char('0') => {
-                self.cursor_x = 0;
-                false
+            let graphemes = self.line_graphemes(y);
+            if x < graphemes.len() && classify_grapheme(graphemes[x], big_word) != CharClass::Whitespace
+            {
+                break;
             }
-            KeyCode::Char('$') => {
-                let line_len = self.buffer[self.cursor_y].len();
-                self.cursor_x = if line_len > 0 { line_len } else { 0 };
-                false
+            if x == 0 && y == 0 {
+                break;
             }
-            KeyCode::Char(':') => {
-                stdout().flush().unwrap();
+        }
 
-                self.prompt_and_execute_command();
-                false
+        let graphemes = self.line_graphemes(y);
+        if x < graphemes.len() {
+            let class = classify_grapheme(graphemes[x], big_word);
+            while x > 0 && classify_grapheme(graphemes[x - 1], big_word) == class {
+                x -= 1;
             }
-            // Quit
-            // move this to command executor at some point
-            KeyCode::Char('q') => true,
-            _ => false,
         }
+
+        self.cursor_y = y;
+        self.cursor_x = x;
     }
 
+    // `e`/`E`: end of the current/next word.
+    fn move_next_word_end(&mut self, big_word: bool) {
+        let mut y = self.cursor_y;
+        let mut x = self.cursor_x;
+        let mut graphemes = self.line_graphemes(y);
+
+        loop {
+            if x + 1 < graphemes.len() {
+                x += 1;
+            } else if y + 1 < self.buffer.len() {
+                y += 1;
+                graphemes = self.line_graphemes(y);
+                x = 0;
+            } else {
+                break;
+            }
+
+            while x < graphemes.len()
+                && classify_grapheme(graphemes[x], big_word) == CharClass::Whitespace
+            {
+                if x + 1 < graphemes.len() {
+                    x += 1;
+                } else if y + 1 < self.buffer.len() {
+                    y += 1;
+                    graphemes = self.line_graphemes(y);
+                    x = 0;
+                } else {
+                    break;
+                }
+            }
+
+            if x < graphemes.len() && classify_grapheme(graphemes[x], big_word) != CharClass::Whitespace
+            {
+                let class = classify_grapheme(graphemes[x], big_word);
+                while x + 1 < graphemes.len()
+                    && classify_grapheme(graphemes[x + 1], big_word) == class
+                {
+                    x += 1;
+                }
+                break;
+            }
+        }
+
+        self.cursor_y = y;
+        self.cursor_x = x;
+    }
+
+
     fn prompt_and_execute_command(&mut self) {
         io::stdout().flush().unwrap();
 
@@ -249,16 +1111,16 @@ impl Editor {
 
         io::stdout().flush().unwrap();
 
-        let command = command.trim();
-        match command {
+        let command = command.trim().to_string();
+        match command.as_str() {
             "w" => self.save_buffer(),
-            "q" => std::process::exit(0),
+            "q" => self.should_quit = self.request_quit(),
             "wq" => {
                 self.save_buffer();
-                std::process::exit(0);
+                self.should_quit = self.request_quit();
             }
             _ => {
-                println!("Unsupported or unknown command: {}", command);
+                self.set_status(format!("Unsupported or unknown command: {}", command));
             }
         }
 
@@ -274,13 +1136,13 @@ impl Editor {
 
                 let mut name = String::new();
                 if io::stdin().read_line(&mut name).is_err() {
-                    println!("Failed to read filename.");
+                    self.set_status("Failed to read filename.".to_string());
                     return;
                 }
 
                 let trimmed_name = name.trim().to_string();
                 if trimmed_name.is_empty() {
-                    println!("Filename cannot be empty!");
+                    self.set_status("Filename cannot be empty!".to_string());
                     return;
                 }
 
@@ -292,121 +1154,128 @@ impl Editor {
             Ok(mut file) => {
                 for line in &self.buffer {
                     if writeln!(file, "{}", line).is_err() {
-                        println!("Failed to write to file.");
+                        self.set_status("Failed to write to file.".to_string());
                         return;
                     }
                 }
 
-                println!("File saved: {}", filename);
+                self.dirty = 0;
+                self.set_status(format!("File saved: {}", filename));
                 self.filename = Some(filename);
             }
             Err(err) => {
-                println!("Failed to create file: {}", err);
+                self.set_status(format!("Failed to create file: {}", err));
             }
         }
     }
-    fn handle_insert_key(&mut self, key: KeyCode) -> bool {
-        match key {
-            KeyCode::Esc => {
-                self.mode = Mode::Normal;
-                // Adjust cursor if at end of line
-                let line_len = self.buffer[self.cursor_y].len();
-                if line_len > 0 && self.cursor_x >= line_len {
-                    self.cursor_x = line_len - 1;
-                }
-                false
-            }
+    // Moves the cursor back onto the last real character when leaving Insert
+    // mode with it past the end of a non-empty line.
+    fn exit_insert_mode(&mut self) {
+        self.mode = Mode::Normal;
+        self.coalescing_insert = false;
+        let line_len = line_len_graphemes(&self.buffer[self.cursor_y]);
+        if line_len > 0 && self.cursor_x >= line_len {
+            self.cursor_x = line_len - 1;
+        }
+    }
 
-            KeyCode::Char(c) => {
-                // Ensure the current line is long enough
-                let line = &mut self.buffer[self.cursor_y];
+    // Self-inserts a typed character. Not bound through the keymap since
+    // it isn't a named command: any `Char` the keymap doesn't claim falls
+    // through to this in `handle_keypress`.
+    fn insert_char(&mut self, c: char) {
+        self.push_undo(true);
+        self.mark_dirty();
+        self.invalidate_highlight_from(self.cursor_y);
 
-                // Insert character
-                if self.cursor_x >= line.len() {
-                    line.push(c);
-                } else {
-                    line.insert(self.cursor_x, c);
-                }
+        // Insert at the byte offset of the current grapheme cluster so
+        // multibyte/combining characters never get split.
+        let line = &mut self.buffer[self.cursor_y];
+        let byte_idx = grapheme_byte_offset(line, self.cursor_x);
+        line.insert(byte_idx, c);
 
-                self.cursor_x += 1;
-                false
-            }
+        self.cursor_x += 1;
+    }
 
-            KeyCode::Backspace => {
-                if self.cursor_x > 0 {
-                    let line = &mut self.buffer[self.cursor_y];
-                    line.remove(self.cursor_x - 1);
-                    self.cursor_x -= 1;
-                } else if self.cursor_y > 0 {
-                    // Join with previous line
-                    let current_line = self.buffer.remove(self.cursor_y);
-                    self.cursor_y -= 1;
-                    self.cursor_x = self.buffer[self.cursor_y].len();
-                    self.buffer[self.cursor_y].push_str(&current_line);
-                }
-                false
-            }
+    fn delete_backward(&mut self) {
+        if self.cursor_x == 0 && self.cursor_y == 0 {
+            return;
+        }
+        self.push_undo(false);
+        self.mark_dirty();
 
-            KeyCode::Enter => {
-                // Split line at cursor
-                let line = &mut self.buffer[self.cursor_y];
-                let new_line = if self.cursor_x < line.len() {
-                    line.split_off(self.cursor_x)
-                } else {
-                    String::new()
-                };
+        if self.cursor_x > 0 {
+            self.invalidate_highlight_from(self.cursor_y);
+            let (start, end) = grapheme_byte_range(&self.buffer[self.cursor_y], self.cursor_x - 1);
+            self.buffer[self.cursor_y].replace_range(start..end, "");
+            self.cursor_x -= 1;
+        } else if self.cursor_y > 0 {
+            // Join with previous line
+            self.invalidate_highlight_from(self.cursor_y - 1);
+            let current_line = self.buffer.remove(self.cursor_y);
+            self.cursor_y -= 1;
+            self.cursor_x = line_len_graphemes(&self.buffer[self.cursor_y]);
+            self.buffer[self.cursor_y].push_str(&current_line);
+        }
+    }
 
-                // Insert new line
-                self.buffer.insert(self.cursor_y + 1, new_line);
-                self.cursor_y += 1;
-                self.cursor_x = 0;
-                false
-            }
+    fn insert_newline(&mut self) {
+        self.push_undo(false);
+        self.mark_dirty();
+        self.invalidate_highlight_from(self.cursor_y);
 
-            // Basic movement
-            KeyCode::Left => {
-                if self.cursor_x > 0 {
-                    self.cursor_x -= 1;
-                }
-                false
-            }
-            KeyCode::Right => {
-                let line_len = self.buffer[self.cursor_y].len();
-                if self.cursor_x < line_len {
-                    self.cursor_x += 1;
-                }
-                false
-            }
-            KeyCode::Up => {
-                if self.cursor_y > 0 {
-                    self.cursor_y -= 1;
-                    let line_len = self.buffer[self.cursor_y].len();
-                    if self.cursor_x > line_len {
-                        self.cursor_x = line_len;
-                    }
-                }
-                false
-            }
-            KeyCode::Down => {
-                if self.cursor_y < self.buffer.len() - 1 {
-                    self.cursor_y += 1;
-                    let line_len = self.buffer[self.cursor_y].len();
-                    if self.cursor_x > line_len {
-                        self.cursor_x = line_len;
-                    }
-                }
-                false
-            }
+        // Split line at cursor
+        let line = &mut self.buffer[self.cursor_y];
+        let line_len = line_len_graphemes(line);
+        let new_line = if self.cursor_x < line_len {
+            let byte_idx = grapheme_byte_offset(line, self.cursor_x);
+            line.split_off(byte_idx)
+        } else {
+            String::new()
+        };
 
-            _ => false,
-        }
+        // Insert new line
+        self.buffer.insert(self.cursor_y + 1, new_line);
+        self.cursor_y += 1;
+        self.cursor_x = 0;
     }
 
-    fn handle_keypress(&mut self, key: KeyCode) -> bool {
-        match self.mode {
-            Mode::Normal => self.handle_normal_key(key),
-            Mode::Insert => self.handle_insert_key(key),
+    // Looks up the action bound to this key in the current mode's keymap
+    // and runs it. A `Char` not claimed by the Insert keymap self-inserts;
+    // anything else unbound is a no-op, same as before this was remappable.
+    fn handle_keypress(&mut self, key: KeyCode, modifiers: KeyModifiers) -> bool {
+        // Search is a keystroke-by-keystroke text prompt, not a set of
+        // remappable commands, so it bypasses the action-dispatch keymaps.
+        if self.mode == Mode::Search {
+            return self.handle_search_key(key);
+        }
+
+        let binding = KeyBinding {
+            code: key,
+            modifiers,
+        };
+        let action_name = match self.mode {
+            Mode::Normal => self.normal_keymap.get(&binding).cloned(),
+            Mode::Insert => self.insert_keymap.get(&binding).cloned(),
+            Mode::Search => None,
+        };
+
+        if let Some(name) = action_name
+            && let Some(action) = self.actions.get(name.as_str()).copied()
+        {
+            // Any bound key (cursor moves included) ends a coalesced run of
+            // typed characters, so one undo step is one contiguous run.
+            self.coalescing_insert = false;
+            action(self);
+            return self.should_quit;
         }
+
+        if self.mode == Mode::Insert
+            && let KeyCode::Char(c) = key
+        {
+            self.insert_char(c);
+        }
+
+        self.should_quit
     }
 }
 
@@ -437,10 +1306,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             {
                 // Check for Ctrl+C to quit
                 if code == KeyCode::Char('c') && modifiers.contains(KeyModifiers::CONTROL) {
-                    should_quit = true;
+                    should_quit = editor.request_quit();
                 } else {
                     // Process regular keypress
-                    should_quit = editor.handle_keypress(code);
+                    should_quit = editor.handle_keypress(code, modifiers);
                 }
             }
         }
@@ -452,3 +1321,97 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Consecutive `insert_char` calls should coalesce into a single undo
+    // step (that's the point of `coalescing_insert`); any bound key, such as
+    // a mode switch, a cursor move, backspace or Enter, resets it, per
+    // chunk1-3's "contiguous run" contract.
+    #[test]
+    fn insert_run_coalesces_into_one_undo_step() {
+        let mut ed = Editor::new(None);
+        ed.insert_char('a');
+        ed.insert_char('b');
+        ed.insert_char('c');
+
+        assert_eq!(ed.undo_stack.len(), 1);
+        assert_eq!(ed.buffer[0], "abc");
+
+        ed.undo();
+        assert_eq!(ed.buffer[0], "");
+    }
+
+    // Leaving and re-entering Insert mode must start a fresh undo group,
+    // so a single `u` after typing two separate words only undoes the
+    // second one.
+    #[test]
+    fn leaving_insert_mode_breaks_undo_coalescing() {
+        let mut ed = Editor::new(None);
+        ed.insert_char('a');
+        ed.insert_char('b');
+        ed.exit_insert_mode();
+        ed.coalescing_insert = false; // mirrors action_insert_mode on re-entry
+        ed.cursor_x = line_len_graphemes(&ed.buffer[0]); // back to end-of-line, like `A` or `$i`
+        ed.insert_char('c');
+        ed.insert_char('d');
+
+        assert_eq!(ed.undo_stack.len(), 2);
+        assert_eq!(ed.buffer[0], "abcd");
+
+        ed.undo();
+        assert_eq!(ed.buffer[0], "ab");
+    }
+
+    // `find_from` must wrap around the end of the buffer back to the start
+    // when the query only occurs before the search origin.
+    #[test]
+    fn find_from_wraps_around_buffer() {
+        let mut ed = Editor::new(None);
+        ed.buffer = vec!["needle here".to_string(), "nothing".to_string()];
+
+        let found = ed.find_from(1, 0, "needle", false);
+        assert_eq!(found, Some((0, 0)));
+    }
+
+    // Backward search should wrap the other way, from the start back to
+    // the last match near the end of the buffer.
+    #[test]
+    fn find_from_backward_wraps_around_buffer() {
+        let mut ed = Editor::new(None);
+        ed.buffer = vec!["nothing".to_string(), "needle here".to_string()];
+
+        let found = ed.find_from(0, 0, "needle", true);
+        assert_eq!(found, Some((1, 0)));
+    }
+
+    // `cursor_x` is a grapheme-cluster index: inserting into a line that
+    // already has a multi-byte grapheme must land the new character after
+    // the whole cluster, not mid-codepoint.
+    #[test]
+    fn insert_char_indexes_by_grapheme_not_byte() {
+        let mut ed = Editor::new(None);
+        ed.buffer = vec!["café".to_string()];
+        ed.cursor_x = line_len_graphemes(&ed.buffer[0]);
+
+        ed.insert_char('!');
+
+        assert_eq!(ed.buffer[0], "café!");
+    }
+
+    // Word motions walk grapheme clusters, not bytes, so a multi-byte
+    // character counts as exactly one cursor step.
+    #[test]
+    fn move_next_word_start_is_grapheme_aware() {
+        let mut ed = Editor::new(None);
+        ed.buffer = vec!["café bar".to_string()];
+        ed.cursor_x = 0;
+
+        ed.move_next_word_start(false);
+
+        assert_eq!(ed.cursor_y, 0);
+        assert_eq!(ed.cursor_x, 5);
+    }
+}